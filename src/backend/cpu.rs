@@ -0,0 +1,105 @@
+use std::rc::Rc;
+
+use skia_safe::{surfaces, Canvas, ColorType, ImageInfo, Surface};
+use softbuffer::{Context, Surface as SoftbufferSurface};
+use winit::{dpi::LogicalSize, event_loop::EventLoop, window::Window, window::WindowBuilder};
+
+use super::RenderBackend;
+
+/// Pure-CPU presentation path: Skia renders into a raster surface, and the pixels are
+/// blitted to the window through `softbuffer` — no GL context involved at all. This is
+/// the guaranteed-working fallback for machines where [`super::GpuBackend`] can't stand
+/// up a GPU surface.
+pub struct CpuBackend {
+    window: Rc<Window>,
+    surface: Surface,
+    softbuffer_surface: SoftbufferSurface<Rc<Window>, Rc<Window>>,
+}
+
+impl CpuBackend {
+    pub fn new(el: &EventLoop<()>) -> Self {
+        let window = Rc::new(
+            WindowBuilder::new()
+                .with_title("rust-skia-gl-window (CPU fallback)")
+                .with_inner_size(LogicalSize::new(800, 800))
+                .build(el)
+                .expect("Could not create window"),
+        );
+
+        // softbuffer's `Context`/`Surface` need to own a handle that keeps the window
+        // alive for as long as they do, so each gets its own clone of the `Rc`.
+        let context =
+            Context::new(window.clone()).expect("Could not create softbuffer context");
+        let mut softbuffer_surface = SoftbufferSurface::new(&context, window.clone())
+            .expect("Could not create softbuffer surface");
+
+        let (width, height) = window.inner_size().into();
+        let surface = Self::create_surface(width, height);
+        Self::resize_softbuffer(&mut softbuffer_surface, width, height);
+
+        Self {
+            window,
+            surface,
+            softbuffer_surface,
+        }
+    }
+
+    fn create_surface(width: u32, height: u32) -> Surface {
+        surfaces::raster_n32_premul((width.max(1) as i32, height.max(1) as i32))
+            .expect("Could not create Skia raster surface")
+    }
+
+    fn resize_softbuffer(
+        softbuffer_surface: &mut SoftbufferSurface<Rc<Window>, Rc<Window>>,
+        width: u32,
+        height: u32,
+    ) {
+        softbuffer_surface
+            .resize(
+                width.max(1).try_into().unwrap(),
+                height.max(1).try_into().unwrap(),
+            )
+            .expect("Could not resize softbuffer surface");
+    }
+}
+
+impl RenderBackend for CpuBackend {
+    fn window(&self) -> &Window {
+        &self.window
+    }
+
+    fn canvas(&mut self) -> &Canvas {
+        self.surface.canvas()
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.surface = Self::create_surface(width, height);
+        Self::resize_softbuffer(&mut self.softbuffer_surface, width, height);
+    }
+
+    fn present(&mut self) {
+        let (width, height) = {
+            let dim = self.surface.image_info().dimensions();
+            (dim.width as u32, dim.height as u32)
+        };
+
+        let info = ImageInfo::new_n32_premul((width as i32, height as i32), None);
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        self.surface
+            .read_pixels(&info, &mut pixels, width as usize * 4, (0, 0));
+
+        let color_type = self.surface.image_info().color_type();
+        let mut buffer = self
+            .softbuffer_surface
+            .buffer_mut()
+            .expect("Could not get softbuffer buffer");
+        for (dst, src) in buffer.iter_mut().zip(pixels.chunks_exact(4)) {
+            let (r, g, b, a) = match color_type {
+                ColorType::BGRA8888 => (src[2], src[1], src[0], src[3]),
+                _ => (src[0], src[1], src[2], src[3]),
+            };
+            *dst = (a as u32) << 24 | (r as u32) << 16 | (g as u32) << 8 | b as u32;
+        }
+        buffer.present().expect("Could not present softbuffer");
+    }
+}
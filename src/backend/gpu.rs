@@ -0,0 +1,233 @@
+use std::{ffi::CString, num::NonZeroU32};
+
+use gl::types::*;
+use glutin::{
+    config::{ConfigTemplateBuilder, GlConfig},
+    context::{ContextApi, ContextAttributesBuilder, PossiblyCurrentContext},
+    display::{GetGlDisplay, GlDisplay},
+    prelude::{GlSurface, NotCurrentGlContext},
+    surface::{Surface as GlutinSurface, SurfaceAttributesBuilder, WindowSurface},
+};
+use glutin_winit::DisplayBuilder;
+use raw_window_handle::HasRawWindowHandle;
+use skia_safe::{
+    gpu::{self, backend_render_targets, gl::FramebufferInfo, SurfaceOrigin},
+    Canvas, ColorType, Surface,
+};
+use winit::{dpi::LogicalSize, event_loop::EventLoop, window::Window, window::WindowBuilder};
+
+use super::RenderBackend;
+
+/// GL-backed presentation path: a Skia `DirectContext` wrapping a glutin window surface.
+///
+/// Guarantees the drop order inside the `FnMut` closure in the caller's event loop.
+/// `window` _must_ be dropped after `gr_context`.
+///
+/// <https://github.com/rust-skia/rust-skia/issues/476>
+pub struct GpuBackend {
+    surface: Surface,
+    gl_surface: GlutinSurface<WindowSurface>,
+    gr_context: gpu::DirectContext,
+    gl_context: PossiblyCurrentContext,
+    window: Window,
+    fb_info: FramebufferInfo,
+    num_samples: usize,
+    stencil_size: usize,
+}
+
+impl GpuBackend {
+    /// Attempts to create a window with a usable GL context and Skia `DirectContext`.
+    /// Returns `Err` if the platform can't provide one (any window already created is
+    /// dropped with it), so the caller can fall back to [`super::CpuBackend`].
+    pub fn new(el: &EventLoop<()>) -> Result<Self, String> {
+        let winit_window_builder = WindowBuilder::new()
+            .with_title("rust-skia-gl-window")
+            .with_inner_size(LogicalSize::new(800, 800));
+
+        let template = ConfigTemplateBuilder::new()
+            .with_alpha_size(8)
+            .with_transparency(true);
+
+        let display_builder =
+            DisplayBuilder::new().with_window_builder(Some(winit_window_builder));
+
+        // `DisplayBuilder::build`'s config picker must return a `Config`, not a
+        // `Result`, so an empty config list (no usable GL config at all — the exact
+        // "Skia can't build a usable GPU surface" scenario this backend exists to
+        // detect) can only surface as a panic from inside the closure. Catch that and
+        // fold it into our own `Result` so the caller still gets a clean `Err` to fall
+        // back to `CpuBackend` with, instead of the panic unwinding past us.
+        // TODO: upstream a Result-returning picker API to glutin_winit so this doesn't
+        // need catch_unwind.
+        let build_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            display_builder.build(el, template, |configs| {
+                // Find the config with the minimum number of samples. Usually Skia takes care of
+                // anti-aliasing and may not be able to create appropriate Surfaces for samples > 0.
+                // See https://github.com/rust-skia/rust-skia/issues/782
+                // And https://github.com/rust-skia/rust-skia/issues/764
+                configs
+                    .reduce(|accum, config| {
+                        let transparency_check = config.supports_transparency().unwrap_or(false)
+                            & !accum.supports_transparency().unwrap_or(false);
+
+                        if transparency_check || config.num_samples() < accum.num_samples() {
+                            config
+                        } else {
+                            accum
+                        }
+                    })
+                    .expect("platform returned no usable GL configs")
+            })
+        }));
+        let (window, gl_config) = match build_result {
+            Ok(result) => result.map_err(|err| format!("could not pick a GL config: {err}"))?,
+            Err(_) => return Err("could not pick a GL config: no usable configs available".to_string()),
+        };
+        println!("Picked a config with {} samples", gl_config.num_samples());
+        let window = window.ok_or("could not create window with OpenGL context")?;
+        let raw_window_handle = window.raw_window_handle();
+
+        // The context creation part. It can be created before surface and that's how
+        // it's expected in multithreaded + multiwindow operation mode, since you
+        // can send NotCurrentContext, but not Surface.
+        let context_attributes = ContextAttributesBuilder::new().build(Some(raw_window_handle));
+
+        // Since glutin by default tries to create OpenGL core context, which may not be
+        // present we should try gles.
+        let fallback_context_attributes = ContextAttributesBuilder::new()
+            .with_context_api(ContextApi::Gles(None))
+            .build(Some(raw_window_handle));
+        let not_current_gl_context = unsafe {
+            gl_config
+                .display()
+                .create_context(&gl_config, &context_attributes)
+                .or_else(|_| {
+                    gl_config
+                        .display()
+                        .create_context(&gl_config, &fallback_context_attributes)
+                })
+                .map_err(|err| format!("failed to create GL context: {err}"))?
+        };
+
+        let (width, height): (u32, u32) = window.inner_size().into();
+
+        let attrs = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+            raw_window_handle,
+            NonZeroU32::new(width).unwrap(),
+            NonZeroU32::new(height).unwrap(),
+        );
+
+        let gl_surface = unsafe {
+            gl_config
+                .display()
+                .create_window_surface(&gl_config, &attrs)
+                .map_err(|err| format!("could not create gl window surface: {err}"))?
+        };
+
+        let gl_context = not_current_gl_context.make_current(&gl_surface).map_err(|err| {
+            format!("could not make GL context current when setting up skia renderer: {err}")
+        })?;
+
+        gl::load_with(|s| {
+            gl_config
+                .display()
+                .get_proc_address(CString::new(s).unwrap().as_c_str())
+        });
+        let interface = gpu::gl::Interface::new_load_with(|name| {
+            if name == "eglGetCurrentDisplay" {
+                return std::ptr::null();
+            }
+            gl_config
+                .display()
+                .get_proc_address(CString::new(name).unwrap().as_c_str())
+        })
+        .ok_or("could not create Skia GL interface")?;
+
+        let mut gr_context = gpu::DirectContext::new_gl(interface, None)
+            .ok_or("could not create Skia direct context")?;
+
+        let fb_info = {
+            let mut fboid: GLint = 0;
+            unsafe { gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut fboid) };
+
+            FramebufferInfo {
+                fboid: fboid.try_into().unwrap(),
+                format: gpu::gl::Format::RGBA8.into(),
+                ..Default::default()
+            }
+        };
+        let num_samples = gl_config.num_samples() as usize;
+        let stencil_size = gl_config.stencil_size() as usize;
+
+        let surface = Self::create_surface(&window, fb_info, &mut gr_context, num_samples, stencil_size);
+
+        Ok(Self {
+            surface,
+            gl_surface,
+            gr_context,
+            gl_context,
+            window,
+            fb_info,
+            num_samples,
+            stencil_size,
+        })
+    }
+
+    fn create_surface(
+        window: &Window,
+        fb_info: FramebufferInfo,
+        gr_context: &mut gpu::DirectContext,
+        num_samples: usize,
+        stencil_size: usize,
+    ) -> Surface {
+        let size = window.inner_size();
+        let size = (
+            size.width.try_into().expect("Could not convert width"),
+            size.height.try_into().expect("Could not convert height"),
+        );
+        let backend_render_target =
+            backend_render_targets::make_gl(size, num_samples, stencil_size, fb_info);
+
+        gpu::surfaces::wrap_backend_render_target(
+            gr_context,
+            &backend_render_target,
+            SurfaceOrigin::BottomLeft,
+            ColorType::RGBA8888,
+            None,
+            None,
+        )
+        .expect("Could not create skia surface")
+    }
+}
+
+impl RenderBackend for GpuBackend {
+    fn window(&self) -> &Window {
+        &self.window
+    }
+
+    fn canvas(&mut self) -> &Canvas {
+        self.surface.canvas()
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.surface = Self::create_surface(
+            &self.window,
+            self.fb_info,
+            &mut self.gr_context,
+            self.num_samples,
+            self.stencil_size,
+        );
+        self.gl_surface.resize(
+            &self.gl_context,
+            NonZeroU32::new(width.max(1)).unwrap(),
+            NonZeroU32::new(height.max(1)).unwrap(),
+        );
+    }
+
+    fn present(&mut self) {
+        self.gr_context.flush_and_submit();
+        self.gl_surface
+            .swap_buffers(&self.gl_context)
+            .expect("Could not swap GL buffers");
+    }
+}
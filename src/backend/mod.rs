@@ -0,0 +1,33 @@
+//! Presentation backends: a way to get pixels drawn with Skia onto the window.
+//!
+//! [`GpuBackend`] wraps a GL `DirectContext` as before; [`CpuBackend`] is a pure
+//! software fallback for machines where Skia can't stand up a usable GPU
+//! surface. `renderer::run` tries the GPU path first and falls back to CPU raster
+//! rendering via `softbuffer` if that fails.
+
+mod cpu;
+mod gpu;
+
+pub use cpu::CpuBackend;
+pub use gpu::GpuBackend;
+
+use skia_safe::Canvas;
+use winit::window::Window;
+
+/// Common seam between the windowing/event loop and however a frame actually
+/// ends up on screen. Implementors own their Skia surface and whatever
+/// presentation resources (GL surface, softbuffer surface, ...) it takes to
+/// show it.
+pub trait RenderBackend {
+    /// The window this backend is presenting into.
+    fn window(&self) -> &Window;
+
+    /// The canvas for the current frame. Draw into this, then call [`present`](Self::present).
+    fn canvas(&mut self) -> &Canvas;
+
+    /// Rebuild any size-dependent resources after the window was resized.
+    fn resize(&mut self, width: u32, height: u32);
+
+    /// Flush the drawn frame and show it.
+    fn present(&mut self);
+}
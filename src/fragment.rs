@@ -0,0 +1,41 @@
+//! Retained scene fragments: record a reusable sub-drawing once into an `SkPicture` and
+//! replay it every frame under a transform, instead of rebuilding its `Path`s each time.
+
+use skia_safe::{Canvas, Picture, PictureRecorder, Rect};
+
+/// A sub-drawing recorded once, in local (untransformed) space, and replayed cheaply
+/// every frame.
+pub struct Fragment {
+    picture: Picture,
+}
+
+impl Fragment {
+    /// Records whatever `draw` does, in local space, into a picture covering `bounds`.
+    pub fn record(bounds: impl Into<Rect>, draw: impl FnOnce(&Canvas)) -> Self {
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(bounds.into(), None);
+        draw(canvas);
+        let picture = recorder
+            .finish_recording_as_picture(None)
+            .expect("Could not finish recording picture");
+        Self { picture }
+    }
+
+    /// Replays the recorded picture onto `canvas` under its current transform.
+    pub fn draw(&self, canvas: &Canvas) {
+        canvas.draw_picture(&self.picture, None, None);
+    }
+}
+
+/// Draws each `(fragment, center, rotation)` entry onto `canvas`, translating that
+/// fragment's local origin to `center` and rotating it by `rotation` degrees under a
+/// `save()`/`restore()` pair.
+pub fn composite(canvas: &Canvas, fragments: &[(&Fragment, (i32, i32), f32)]) {
+    for (fragment, center, rotation) in fragments {
+        canvas.save();
+        canvas.translate(skia_safe::Point::from(*center));
+        canvas.rotate(*rotation, None);
+        fragment.draw(canvas);
+        canvas.restore();
+    }
+}
@@ -0,0 +1,70 @@
+//! Headless export: render the animation loop to disk without a window or event loop.
+//!
+//! Each frame is rendered into an offscreen Skia raster surface (no GPU, no display
+//! server required), snapshotted, and encoded as a PNG. The same frames' pixels are
+//! also read back and assembled into a single looping GIF.
+
+use std::{fs, io, path::Path};
+
+use gif::{Encoder as GifEncoder, Frame as GifFrame, Repeat};
+use skia_safe::{surfaces, Color, EncodedImageFormat};
+
+use crate::{animation_frame_count, IconScene};
+
+/// Renders one full loop of the icon animation to `out_dir` as `frame_0000.png`,
+/// `frame_0001.png`, ... and a looping `animation.gif` alongside them.
+pub fn export_frames(out_dir: &Path, size: i32, fps: usize, bpm: usize) -> io::Result<()> {
+    fs::create_dir_all(out_dir)?;
+
+    let frame_count = animation_frame_count(fps, bpm);
+    let digits = frame_count.max(1).to_string().len().max(4);
+    let scene = IconScene::new(size);
+
+    let mut gif_frames = Vec::with_capacity(frame_count);
+
+    for frame in 0..frame_count {
+        let mut surface = surfaces::raster_n32_premul((size, size))
+            .expect("Could not create Skia raster surface");
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        scene.draw(canvas, frame, fps, bpm);
+
+        let image = surface.image_snapshot();
+        let data = image
+            .encode(None, EncodedImageFormat::PNG, None)
+            .expect("Could not encode frame as PNG");
+
+        let path = out_dir.join(format!("frame_{frame:0digits$}.png"));
+        fs::write(&path, data.as_bytes())?;
+
+        let mut pixels = vec![0u8; (size * size * 4) as usize];
+        let info = surface.image_info().with_color_type(skia_safe::ColorType::RGBA8888);
+        surface.read_pixels(&info, &mut pixels, size as usize * 4, (0, 0));
+        gif_frames.push(pixels);
+    }
+
+    write_gif(&out_dir.join("animation.gif"), size as u16, size as u16, fps, &gif_frames)
+}
+
+fn write_gif(path: &Path, width: u16, height: u16, fps: usize, frames: &[Vec<u8>]) -> io::Result<()> {
+    let file = fs::File::create(path)?;
+    let mut encoder =
+        GifEncoder::new(file, width, height, &[]).expect("Could not create GIF encoder");
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .expect("Could not set GIF repeat mode");
+
+    // GIF delay is in hundredths of a second, so it has to be derived from the same
+    // `fps` the frames were rendered at, or the exported animation plays back at the
+    // wrong speed.
+    let delay = (100.0 / fps as f32).round() as u16;
+    for pixels in frames {
+        let mut frame = GifFrame::from_rgba_speed(width, height, &mut pixels.clone(), 10);
+        frame.delay = delay;
+        encoder
+            .write_frame(&frame)
+            .expect("Could not write GIF frame");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Library-level renderer: owns all the window/GL/Skia plumbing and drives a caller-supplied
+//! draw closure, so this crate can be used as a rendering backend for arbitrary scene code
+//! rather than just the bundled icon animation.
+
+use std::time::{Duration, Instant};
+
+use winit::{
+    event::{Event, KeyEvent, Modifiers, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+};
+
+use skia_safe::Canvas;
+
+use crate::backend::{CpuBackend, GpuBackend, RenderBackend};
+
+/// Opens a window and runs the 20 FPS Skia render loop, calling `draw` once per frame
+/// with that frame's canvas and a frame index that advances by one per drawn frame (any
+/// key press steps it back by 10).
+///
+/// Handles backend selection (GPU with a CPU/softbuffer fallback), the [`GpuBackend`]
+/// drop-ordering workaround, and resize handling, so callers only need to supply
+/// drawing logic.
+pub fn run(mut draw: impl FnMut(&Canvas, usize) + 'static) {
+    let el = EventLoop::new().expect("Failed to create event loop");
+
+    let mut env: Box<dyn RenderBackend> = match GpuBackend::new(&el) {
+        Ok(gpu) => Box::new(gpu),
+        Err(err) => {
+            eprintln!("GPU backend unavailable ({err}), falling back to CPU raster backend");
+            Box::new(CpuBackend::new(&el))
+        }
+    };
+
+    let mut frame = 0usize;
+    let mut previous_frame_start = Instant::now();
+    let mut modifiers = Modifiers::default();
+
+    el.run(move |event, window_target| {
+        let frame_start = Instant::now();
+        let mut draw_frame = false;
+
+        if let Event::WindowEvent { event, .. } = event {
+            match event {
+                WindowEvent::CloseRequested => {
+                    window_target.exit();
+                    return;
+                }
+                WindowEvent::Resized(physical_size) => {
+                    let (width, height): (u32, u32) = physical_size.into();
+                    env.resize(width, height);
+                }
+                WindowEvent::ModifiersChanged(new_modifiers) => modifiers = new_modifiers,
+                WindowEvent::KeyboardInput {
+                    event: KeyEvent { logical_key, .. },
+                    ..
+                } => {
+                    if modifiers.state().super_key() && logical_key == "q" {
+                        window_target.exit();
+                    }
+                    frame = frame.saturating_sub(10);
+                    env.window().request_redraw();
+                }
+                WindowEvent::RedrawRequested => {
+                    draw_frame = true;
+                }
+                _ => (),
+            }
+        }
+        let expected_frame_length_seconds = 1.0 / 20.0;
+        let frame_duration = Duration::from_secs_f32(expected_frame_length_seconds);
+
+        if frame_start - previous_frame_start > frame_duration {
+            draw_frame = true;
+            previous_frame_start = frame_start;
+        }
+        if draw_frame {
+            frame += 1;
+            draw(env.canvas(), frame);
+            env.present();
+        }
+
+        window_target.set_control_flow(ControlFlow::WaitUntil(
+            previous_frame_start + frame_duration,
+        ))
+    })
+    .expect("run() failed");
+}
@@ -0,0 +1,94 @@
+//! Stroke styling: dash intervals, caps, and joins layered onto Skia's `Paint` stroke
+//! controls, so the outline draws in `chain_ring_fragment`/`draw_triangle_outline` can be customized without
+//! touching their geometry code.
+
+use skia_safe::{dash_path_effect, Paint, PaintCap, PaintJoin};
+
+/// A dash pattern: alternating on/off lengths walked along the stroked outline,
+/// starting `phase` units in.
+#[derive(Debug, Clone)]
+pub struct Dash {
+    pub intervals: Vec<f32>,
+    pub phase: f32,
+}
+
+/// Stroke appearance for outline draws: cap, join, miter limit, and an optional dash
+/// pattern. [`StrokeStyle::default`] reproduces a plain solid stroke with Skia's own
+/// defaults (butt caps, miter joins).
+#[derive(Debug, Clone)]
+pub struct StrokeStyle {
+    pub cap: PaintCap,
+    pub join: PaintJoin,
+    pub miter_limit: f32,
+    pub dash: Option<Dash>,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            cap: PaintCap::Butt,
+            join: PaintJoin::Miter,
+            miter_limit: 4.0,
+            dash: None,
+        }
+    }
+}
+
+impl StrokeStyle {
+    /// Applies cap, join, miter limit, and dash pattern to `paint`. Does not touch
+    /// stroke width or paint style, since those vary with canvas size and fill intent.
+    pub fn apply(&self, paint: &mut Paint) {
+        paint.set_stroke_cap(self.cap);
+        paint.set_stroke_join(self.join);
+        paint.set_stroke_miter(self.miter_limit);
+        paint.set_path_effect(
+            self.dash
+                .as_ref()
+                .and_then(|dash| dash_path_effect::new(&dash.intervals, dash.phase)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_sets_cap_join_and_miter() {
+        let style = StrokeStyle {
+            cap: PaintCap::Round,
+            join: PaintJoin::Bevel,
+            miter_limit: 2.5,
+            dash: None,
+        };
+        let mut paint = Paint::default();
+        style.apply(&mut paint);
+
+        assert_eq!(paint.stroke_cap(), PaintCap::Round);
+        assert_eq!(paint.stroke_join(), PaintJoin::Bevel);
+        assert_eq!(paint.stroke_miter(), 2.5);
+        assert!(paint.path_effect().is_none());
+    }
+
+    #[test]
+    fn apply_without_dash_clears_any_previous_path_effect() {
+        let mut paint = Paint::default();
+        paint.set_path_effect(dash_path_effect::new(&[1.0, 1.0], 0.0));
+        assert!(paint.path_effect().is_some());
+
+        StrokeStyle::default().apply(&mut paint);
+        assert!(paint.path_effect().is_none());
+    }
+
+    #[test]
+    fn apply_with_dash_sets_a_path_effect() {
+        let style = StrokeStyle {
+            dash: Some(Dash { intervals: vec![4.0, 2.0], phase: 0.0 }),
+            ..StrokeStyle::default()
+        };
+        let mut paint = Paint::default();
+        style.apply(&mut paint);
+
+        assert!(paint.path_effect().is_some());
+    }
+}
@@ -0,0 +1,262 @@
+//! Minimal SVG `<path>` loader: turns `d="..."` geometry and flat `fill`/`stroke` colors
+//! into `skia_safe::Path`s that can be scaled and centered into the window like the
+//! bundled icon.
+//!
+//! Only plain hex colors are understood (gradients, `currentColor`, named colors are
+//! ignored); this is a viewer for simple vector icons, not a full SVG renderer.
+
+use std::{fs, io, path::Path as FsPath};
+
+use skia_safe::{utils::parse_path, Canvas, Color, Matrix, Paint, PaintCap, PaintJoin, PaintStyle, Path, Rect};
+
+use crate::stroke::{Dash, StrokeStyle};
+use crate::PEN_SIZE;
+
+/// A single `<path>` element: its outline plus any flat fill/stroke color and stroke
+/// styling (`stroke-dasharray`, `stroke-linecap`, `stroke-linejoin`) it declared.
+pub struct SvgShape {
+    pub path: Path,
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub stroke_style: StrokeStyle,
+}
+
+/// The shapes parsed out of an SVG file, plus their combined bounds in document space.
+pub struct SvgDocument {
+    pub shapes: Vec<SvgShape>,
+    pub bounds: Rect,
+}
+
+/// Loads every `<path>` element's `d`, `fill`, and `stroke` attributes from an SVG file.
+pub fn load(path: &FsPath) -> io::Result<SvgDocument> {
+    let xml = fs::read_to_string(path)?;
+    let mut shapes: Vec<SvgShape> = Vec::new();
+    let mut bounds = Rect::new_empty();
+
+    for tag in find_tags(&xml, "path") {
+        let Some(d) = attribute(tag, "d") else {
+            continue;
+        };
+        let Some(svg_path) = parse_path::from_svg(d) else {
+            continue;
+        };
+
+        if shapes.is_empty() {
+            bounds = *svg_path.bounds();
+        } else {
+            bounds.join(*svg_path.bounds());
+        }
+
+        shapes.push(SvgShape {
+            path: svg_path,
+            fill: attribute(tag, "fill").and_then(parse_color),
+            stroke: attribute(tag, "stroke").and_then(parse_color),
+            stroke_style: parse_stroke_style(tag),
+        });
+    }
+
+    Ok(SvgDocument { shapes, bounds })
+}
+
+/// Draws `document` scaled and centered to fit within `size`, reusing the demo's stroke
+/// width convention (scaled to the canvas, never thinner than [`PEN_SIZE`]).
+pub fn render(document: &SvgDocument, canvas: &Canvas, size: (i32, i32)) {
+    if document.shapes.is_empty()
+        || document.bounds.width() <= 0.0
+        || document.bounds.height() <= 0.0
+    {
+        return;
+    }
+
+    let scale = (size.0 as f32 / document.bounds.width())
+        .min(size.1 as f32 / document.bounds.height());
+    let offset_x = (size.0 as f32 - document.bounds.width() * scale) / 2.0;
+    let offset_y = (size.1 as f32 - document.bounds.height() * scale) / 2.0;
+
+    let mut matrix = Matrix::scale((scale, scale));
+    matrix.pre_translate((-document.bounds.left, -document.bounds.top));
+    matrix.post_translate((offset_x, offset_y));
+
+    canvas.save();
+    canvas.concat(&matrix);
+
+    // Divided by `scale` because the width is applied under the document-to-canvas matrix.
+    let stroke_width =
+        PEN_SIZE.max(canvas.image_info().dimensions().width as f32 / 360.0) / scale;
+    for shape in &document.shapes {
+        if let Some(fill) = shape.fill {
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(fill);
+            canvas.draw_path(&shape.path, &paint);
+        }
+        if let Some(stroke) = shape.stroke {
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(stroke_width);
+            paint.set_color(stroke);
+            shape.stroke_style.apply(&mut paint);
+            canvas.draw_path(&shape.path, &paint);
+        }
+    }
+
+    canvas.restore();
+}
+
+fn find_tags<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let open = format!("<{name}");
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(open.as_str()) {
+        let after = &rest[start..];
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        tags.push(&after[..=end]);
+        rest = &after[end + 1..];
+    }
+    tags
+}
+
+/// Finds `name="..."` as a whole attribute token, not just a substring: a raw
+/// `tag.find("d=\"")` would also match inside `id="..."`, since `"id=\""` contains the
+/// literal text `"d=\""`. An attribute only counts if the character right before its
+/// name isn't part of a longer identifier (another letter, digit, `-`, `_`, or `:`).
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=\"");
+    let mut search_from = 0;
+    loop {
+        let found = tag[search_from..].find(needle.as_str())?;
+        let start = search_from + found;
+        let is_whole_token = match tag[..start].chars().next_back() {
+            Some(c) => !(c.is_alphanumeric() || c == '-' || c == '_' || c == ':'),
+            None => true,
+        };
+        if is_whole_token {
+            let value_start = start + needle.len();
+            let end = tag[value_start..].find('"')? + value_start;
+            return Some(&tag[value_start..end]);
+        }
+        search_from = start + 1;
+    }
+}
+
+/// Parses a `#rgb`, `#rrggbb`, or `#rrggbbaa` hex color. Values that aren't hex at all
+/// (`none`, `currentColor`, named colors, `rgb(...)`) are deliberately unsupported and
+/// skipped quietly, as documented at the top of this module; a value that *looks* like
+/// hex but doesn't parse is logged, since that's far more likely to be a typo.
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    let color = match hex.len() {
+        3 => {
+            let expand = |c: char| u8::from_str_radix(&c.to_string().repeat(2), 16).ok();
+            let mut chars = hex.chars();
+            (|| {
+                let (r, g, b) = (chars.next()?, chars.next()?, chars.next()?);
+                let (r, g, b) = (expand(r)?, expand(g)?, expand(b)?);
+                Some(Color::from_argb(0xff, r, g, b))
+            })()
+        }
+        6 => u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|rgb| Color::from(0xff00_0000 | rgb)),
+        // SVG/CSS `#rrggbbaa` puts alpha in the last byte, but `Color::from(u32)` is
+        // ARGB (alpha in the top byte) like everywhere else in this codebase, so the
+        // parsed bytes need rotating into place: 0xRRGGBBAA -> 0xAARRGGBB.
+        8 => u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|rgba| Color::from((rgba << 24) | (rgba >> 8))),
+        _ => None,
+    };
+    if color.is_none() {
+        eprintln!("svg: could not parse color {value:?}, ignoring it");
+    }
+    color
+}
+
+/// Builds a [`StrokeStyle`] from a `<path>` tag's `stroke-dasharray`, `stroke-dashoffset`,
+/// `stroke-linecap`, and `stroke-linejoin` attributes, falling back to
+/// [`StrokeStyle::default`] for whichever of them aren't present.
+fn parse_stroke_style(tag: &str) -> StrokeStyle {
+    let mut style = StrokeStyle::default();
+
+    if let Some(cap) = attribute(tag, "stroke-linecap") {
+        style.cap = match cap {
+            "round" => PaintCap::Round,
+            "square" => PaintCap::Square,
+            _ => PaintCap::Butt,
+        };
+    }
+    if let Some(join) = attribute(tag, "stroke-linejoin") {
+        style.join = match join {
+            "round" => PaintJoin::Round,
+            "bevel" => PaintJoin::Bevel,
+            _ => PaintJoin::Miter,
+        };
+    }
+    if let Some(dasharray) = attribute(tag, "stroke-dasharray") {
+        let intervals: Vec<f32> = dasharray
+            .split([',', ' '])
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        if !intervals.is_empty() {
+            let phase = attribute(tag, "stroke-dashoffset")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0);
+            style.dash = Some(Dash { intervals, phase });
+        }
+    }
+
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_tags_collects_every_matching_element() {
+        let xml = r#"<svg><path d="M0 0"/><path d="M1 1"/></svg>"#;
+        let tags = find_tags(xml, "path");
+        assert_eq!(tags, vec![r#"<path d="M0 0"/>"#, r#"<path d="M1 1"/>"#]);
+    }
+
+    #[test]
+    fn find_tags_ignores_other_element_names() {
+        assert!(find_tags("<svg><rect/></svg>", "path").is_empty());
+    }
+
+    #[test]
+    fn attribute_extracts_a_quoted_value() {
+        let tag = r#"<path d="M0 0" fill="#ff0000"/>"#;
+        assert_eq!(attribute(tag, "fill"), Some("#ff0000"));
+        assert_eq!(attribute(tag, "stroke"), None);
+    }
+
+    #[test]
+    fn attribute_does_not_match_a_name_that_is_only_a_suffix_of_another_attribute() {
+        // A naive `tag.find("d=\"")` would match inside `id="..."` here, since
+        // `"id=\""` contains the literal text `"d=\""`.
+        let tag = r#"<path id="shape1" d="M0 0 L10 10 Z"/>"#;
+        assert_eq!(attribute(tag, "d"), Some("M0 0 L10 10 Z"));
+        assert_eq!(attribute(tag, "id"), Some("shape1"));
+    }
+
+    #[test]
+    fn parse_color_handles_3_6_and_8_digit_hex() {
+        assert_eq!(parse_color("#f00"), Some(Color::from_argb(0xff, 0xff, 0x00, 0x00)));
+        assert_eq!(parse_color("#ff0000"), Some(Color::from(0xffff_0000)));
+        // #ff000080 is semi-transparent red per SVG/CSS (R=ff, G=00, B=00, A=80).
+        assert_eq!(parse_color("#ff000080"), Some(Color::from_argb(0x80, 0xff, 0x00, 0x00)));
+    }
+
+    #[test]
+    fn parse_color_rejects_non_hex_and_malformed_hex() {
+        assert_eq!(parse_color("none"), None);
+        assert_eq!(parse_color("currentColor"), None);
+        assert_eq!(parse_color("#zzzzzz"), None);
+    }
+}